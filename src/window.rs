@@ -1,55 +1,131 @@
 
 use std;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
+#[macro_use]
+extern crate lazy_static;
 extern crate sdl2;
 extern crate sdl2_image;
+extern crate image;
 use sdl2::render;
 use sdl2::video;
-use sdl2_image::LoadTexture;
 
 use event::{self,Event};
 use shape;
 
+// SDL2 init/quit is global, and it's not safe to de-init SDL2 while any Window is still using it.
+// So instead of each Window owning its own `Sdl` context, they all share a single one stashed
+// here, reference-counted by `WINDOW_COUNT`. The first `Window::new` call initializes SDL and
+// SDL_image; the last Window to drop tears them back down.
+lazy_static! {
+    static ref SDL_CONTEXT: Mutex<Option<Arc<sdl2::sdl::Sdl>>> = Mutex::new(None);
+}
+static WINDOW_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// The OS event queue is shared by every Window in the process (there's only one `event_pump`),
+// so whichever Window calls `next_frame()` first in a tick drains *all* of it. To still route
+// events to the right Window, that drain sorts events into per-window-id buckets here, and each
+// Window's `next_frame()` only consumes its own bucket. `Quit` isn't tied to any one window, so
+// it's tracked separately and delivered to every Window.
+lazy_static! {
+    static ref PENDING_EVENTS: Mutex<HashMap<u32, std::vec::Vec<Event>>> = Mutex::new(HashMap::new());
+}
+static QUIT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Drain every event currently queued by SDL and sort it into `PENDING_EVENTS` by window id.
+/// Events with no window id of their own (rare, but possible) are attributed to `current_window_id`
+/// rather than dropped.
+fn pump_os_events(context: &sdl2::sdl::Sdl, current_window_id: u32) {
+    let mut buckets = PENDING_EVENTS.lock().unwrap();
+    loop {
+        match context.event_pump().poll_event() {
+            None => break,
+            Some(sdl_event) => {
+                let window_id = sdl_event.get_window_id().unwrap_or(current_window_id);
+                match Event::from_sdl2_event(sdl_event) {
+                    Some(Event::Quit) => QUIT_REQUESTED.store(true, Ordering::SeqCst),
+                    Some(e) => buckets.entry(window_id).or_insert_with(std::vec::Vec::new).push(e),
+                    None => (),
+                }
+            }
+        }
+    }
+}
+
+fn acquire_sdl_context() -> Arc<sdl2::sdl::Sdl> {
+    let mut guard = SDL_CONTEXT.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(Arc::new(sdl2::init(sdl2::INIT_EVERYTHING).unwrap()));
+        sdl2_image::init(sdl2_image::InitFlag::all());
+    }
+    guard.as_ref().unwrap().clone()
+}
+
+/// Record that a Window has finished constructing and is now sharing the global SDL context.
+/// Call this only once construction can no longer fail (`video::Window::new`, `gl_create_context`,
+/// etc. have all already succeeded) so a panic part-way through `Window::new`/`new_opengl` can't
+/// leave `WINDOW_COUNT` counting a Window that was never actually created.
+fn register_window() {
+    WINDOW_COUNT.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Release this Window's share of the global SDL context. Only the last Window to drop actually
+/// quits SDL_image, frees the shared `Sdl`, and resets the shared event-routing state, so a
+/// later, unrelated Window doesn't inherit a stale `Quit` from a program that already ran and
+/// closed one.
+fn release_sdl_context() {
+    if WINDOW_COUNT.fetch_sub(1, Ordering::SeqCst) == 1 {
+        sdl2_image::quit();
+        *SDL_CONTEXT.lock().unwrap() = None;
+        QUIT_REQUESTED.store(false, Ordering::SeqCst);
+    }
+}
+
 ///
 /// A Window can display graphics, play sounds, and handle events.
 ///
-/// Creating multiple Windows is untested!
+/// You can create as many Windows as you like; SDL is initialized once for the whole process and
+/// torn down when the last Window is dropped. Events are routed to the Window whose SDL window id
+/// they were generated for, so each Window only sees its own input.
 ///
 pub struct Window {
     // sdl graphics
-    context:                    sdl2::sdl::Sdl,
-    renderer:                   render::Renderer,
+    context:                    Arc<sdl2::sdl::Sdl>,
+    window_id:                  u32,
+
+    // Present for a Window created with `new`; absent for one created with `new_opengl`, which
+    // manages its own rendering instead of going through the SDL renderer.
+    renderer:                   Option<render::Renderer>,
+    texture_creator:            Option<std::rc::Rc<render::TextureCreator>>,
+
+    // The reverse: present only for a Window created with `new_opengl`.
+    gl_window:                  Option<video::Window>,
+    gl_context:                 Option<video::GLContext>,
 
     // events and event logic
     running:                    bool,
     event_queue:                std::vec::Vec<Event>,
 
     // timing
-    target_ticks_per_frame:     u32,
+    dt:                         u32,
+    accumulator:                u32,
+    pending_steps:              u32,
     ticks_at_previous_frame:    u32,
 }
 
+/// Frame times longer than this are clamped before being fed to the accumulator, so a single
+/// slow frame (a GC pause, a breakpoint, the window being dragged) can't make us try to "catch
+/// up" with a burst of update steps next frame (the "spiral of death").
+const MAX_FRAME_TIME_MS: u32 = 250;
+
 /// Top-level Running / Creation Methods
 /// ------------------------------------
 impl Window {
     /// Intialize a new running window. `name` is used as a caption.
     pub fn new(name: &str, width: i32, height: i32) -> Self {
-        // SDL2 Initialization calls. This section here is the reason we can't easily create
-        // multiple Windows. There would have to be some kind of global variable that tracked
-        // whether SDL2 had already been init'd.
-        //
-        // Note that initialization is not the only problem. SDL2 is usually safe to init
-        // multiple times, but it's not safe to de-init SDL2 and then continue using it. We'd
-        // either have to have an explicit Deinitialize() global function or keep a global count
-        // of windows that exist.
-        //
-        // Both solutions are ugly and error-prone, and would probably break thread safety. Going
-        // to assume that there will only be one Window per program.
-        //
-        // TODO: solve this problem
-        //
-        let sdl_context = sdl2::init(sdl2::INIT_EVERYTHING).unwrap();
-        sdl2_image::init(sdl2_image::InitFlag::all());
+        let sdl_context = acquire_sdl_context();
         let sdl_window = video::Window::new(
             name,
             video::WindowPos::PosUndefined,
@@ -57,56 +133,149 @@ impl Window {
             width, height,
             video::SHOWN,
         ).unwrap();
+        let window_id = sdl_window.get_id();
 
         let renderer = render::Renderer::from_window(
             sdl_window,
             render::RenderDriverIndex::Auto,
             render::ACCELERATED,
         ).unwrap();
+        // Textures are created through this instead of directly through the renderer so that an
+        // `Image` can own everything it needs to stay valid (see `load_image`) without being tied
+        // to a borrow of the Window itself.
+        let texture_creator = std::rc::Rc::new(renderer.texture_creator());
 
         let window = Window{
             context:                    sdl_context,
-            renderer:                   renderer,
+            window_id:                  window_id,
+            renderer:                   Some(renderer),
+            texture_creator:            Some(texture_creator),
+            gl_window:                  None,
+            gl_context:                 None,
             running:                    true,
             event_queue:                vec![],
-            target_ticks_per_frame:     (1000.0 / 60.0) as u32,
-            ticks_at_previous_frame:    0,
+            dt:                         (1000.0 / 60.0) as u32,
+            accumulator:                0,
+            pending_steps:              0,
+            ticks_at_previous_frame:    sdl2::timer::get_ticks(),
         };
+        register_window();
         window.clear();
         window
     }
 
-    /// Redrawing and update the display, while maintaining a consistent framerate and updating the
-    /// event queue. You should draw your objects immediately before you call this function. NOTE:
-    /// This function returns false if the program should terminate.
+    /// Initialize a new running window that exposes a raw OpenGL context instead of the SDL
+    /// renderer, for users who want to drive their own rendering with `gl`/`glow`/similar bindings
+    /// (e.g. shader-based rendering). `name` is used as a caption.
+    ///
+    /// Drawing methods like `draw_rect` and `draw_image`, and types built on the SDL renderer like
+    /// `PixelBuffer`, are not available on a Window created this way. Use `gl_get_proc_address` to
+    /// load GL functions, and draw with them directly between calls to `next_frame`, which swaps
+    /// the GL buffers instead of presenting the SDL renderer.
+    pub fn new_opengl(name: &str, width: i32, height: i32) -> Self {
+        let sdl_context = acquire_sdl_context();
+        let sdl_window = video::Window::new(
+            name,
+            video::WindowPos::PosUndefined,
+            video::WindowPos::PosUndefined,
+            width, height,
+            video::SHOWN | video::OPENGL,
+        ).unwrap();
+        let window_id = sdl_window.get_id();
+        let gl_context = sdl_window.gl_create_context().unwrap();
+
+        let window = Window{
+            context:                    sdl_context,
+            window_id:                  window_id,
+            renderer:                   None,
+            texture_creator:            None,
+            gl_window:                  Some(sdl_window),
+            gl_context:                 Some(gl_context),
+            running:                    true,
+            event_queue:                vec![],
+            dt:                         (1000.0 / 60.0) as u32,
+            accumulator:                0,
+            pending_steps:              0,
+            ticks_at_previous_frame:    sdl2::timer::get_ticks(),
+        };
+        register_window();
+        window
+    }
+
+    /// Look up an OpenGL function pointer by name, for use with `gl`/`glow`/similar bindings. Only
+    /// meaningful on a Window created with `new_opengl`.
+    pub fn gl_get_proc_address(&self, proc_name: &str) -> *const () {
+        video::gl_get_proc_address(proc_name)
+    }
+
+    /// Make this Window's GL context current on the calling thread. Only needed if you're juggling
+    /// more than one OpenGL Window at once.
+    pub fn gl_make_current(&self) {
+        let gl_window = self.gl_window.as_ref().expect("gl_make_current requires a Window created with new_opengl");
+        let gl_context = self.gl_context.as_ref().expect("gl_make_current requires a Window created with new_opengl");
+        gl_window.gl_make_current(gl_context).unwrap();
+    }
+
+    fn renderer(&self) -> &render::Renderer {
+        self.renderer.as_ref().expect("this operation requires a Window created with new, not new_opengl")
+    }
+
+    fn texture_creator(&self) -> &std::rc::Rc<render::TextureCreator> {
+        self.texture_creator.as_ref().expect("this operation requires a Window created with new, not new_opengl")
+    }
+
+    /// Redrawing and update the display, while advancing the fixed-timestep accumulator and
+    /// updating the event queue. You should draw your objects immediately before you call this
+    /// function. NOTE: This function returns false if the program should terminate.
+    ///
+    /// This does not block to cap the framerate: instead it measures however long the last frame
+    /// actually took and turns that into a number of whole simulation steps (see
+    /// `update_steps()`) so that physics and game logic run at a consistent rate regardless of
+    /// how fast or slow rendering is. Typical usage:
+    ///
+    /// ```ignore
+    /// while window.next_frame() {
+    ///     for _ in 0..window.update_steps() {
+    ///         world.step();
+    ///     }
+    ///     world.draw(&window, window.interpolation_alpha());
+    /// }
+    /// ```
     pub fn next_frame(&mut self) -> bool {
         if !self.running {
             return false;
         }
 
-        self.renderer.drawer().present();
-
-        let mut current_ticks = sdl2::timer::get_ticks();
-        while current_ticks - self.ticks_at_previous_frame < self.target_ticks_per_frame {
-            sdl2::timer::delay(5);
-            current_ticks = sdl2::timer::get_ticks();
+        match self.gl_window {
+            Some(ref gl_window) => gl_window.gl_swap_window(),
+            None => self.renderer().drawer().present(),
         }
+
+        let current_ticks = sdl2::timer::get_ticks();
+        let frame_time = std::cmp::min(current_ticks - self.ticks_at_previous_frame, MAX_FRAME_TIME_MS);
         self.ticks_at_previous_frame = current_ticks;
 
-        // Handle events
-        loop {
-            let sdl_event = self.context.event_pump().poll_event();
-            match sdl_event {
-                None => break,
-                Some(sdl_event) => match Event::from_sdl2_event(sdl_event) {
-                    Some(Event::Quit) => self.quit(),
-                    Some(Event::Keyboard{key: event::KeyCode::Escape, ..})  => self.quit(),
-
-                    // any other unrecognized event
-                    Some(e) => (self.event_queue.push(e)),
-                    None => (),
-                },
-            };
+        self.accumulator += frame_time;
+        self.pending_steps = 0;
+        while self.accumulator >= self.dt {
+            self.pending_steps += 1;
+            self.accumulator -= self.dt;
+        }
+
+        // Pump whatever SDL has queued into the shared per-window buckets (a no-op if some other
+        // Window already did this for the current tick), then take only the events meant for us.
+        pump_os_events(&self.context, self.window_id);
+
+        if QUIT_REQUESTED.load(Ordering::SeqCst) {
+            self.quit();
+        }
+
+        let our_events = PENDING_EVENTS.lock().unwrap().remove(&self.window_id).unwrap_or_else(std::vec::Vec::new);
+        for e in our_events {
+            match e {
+                Event::Keyboard{key: event::KeyCode::Escape, ..} => self.quit(),
+                e => self.event_queue.push(e),
+            }
         }
 
         true
@@ -129,6 +298,53 @@ impl Window {
     pub fn quit(&mut self) {
         self.running = false;
     }
+
+    /// The number of fixed-length simulation steps that should be run to catch the game logic up
+    /// to the current frame. Call `next_frame()` first; this reflects the accumulator state as of
+    /// that call.
+    pub fn update_steps(&self) -> u32 {
+        self.pending_steps
+    }
+
+    /// How far between the previous and next simulation step the current frame falls, as a value
+    /// in `[0.0, 1.0)`. Use this to interpolate rendered positions between updates so motion stays
+    /// smooth even though the simulation itself only advances in discrete `dt`-sized steps.
+    pub fn interpolation_alpha(&self) -> f64 {
+        self.accumulator as f64 / self.dt as f64
+    }
+
+    /// Set the rate at which `update_steps()` advances the simulation, in steps per second. This
+    /// does not affect how often `next_frame` is called or how often the screen is redrawn.
+    ///
+    /// Panics if `fps` is 0, since that would produce an infinitely long simulation step. `dt` is measured in whole milliseconds, so `fps` above 1000 is
+    /// clamped to a 1ms step rather than being allowed to round down to 0 (which would make the
+    /// accumulator loop in `next_frame` spin forever).
+    pub fn set_target_fps(&mut self, fps: u32) {
+        assert!(fps > 0, "set_target_fps: fps must be greater than 0");
+        self.dt = std::cmp::max(1, (1000.0 / fps as f64) as u32);
+    }
+
+    /// Run the Window's main loop for you, calling `callback` once per frame with all of the
+    /// events that arrived during that frame. This is equivalent to hand-writing:
+    ///
+    /// ```ignore
+    /// while window.next_frame() {
+    ///     let mut events = vec![];
+    ///     while window.has_event() {
+    ///         events.push(window.next_event());
+    ///     }
+    ///     callback(&mut window, &events);
+    /// }
+    /// ```
+    ///
+    /// `run` returns once the loop ends, i.e. once `quit()` is called (directly, or by the user
+    /// closing the window or pressing Escape).
+    pub fn run<F: FnMut(&mut Window, &[Event])>(mut self, mut callback: F) {
+        while self.next_frame() {
+            let events: std::vec::Vec<Event> = self.event_queue.drain(..).collect();
+            callback(&mut self, &events);
+        }
+    }
 }
 
 /// Drawing Methods
@@ -138,22 +354,22 @@ impl Window {
     /// operation. To "unset" the color, call set_color with (255,255,255,255)
     pub fn set_color(&self, red: u8, green: u8, blue: u8, alpha: u8) {
         let color_struct = sdl2::pixels::Color::RGBA(red, green, blue, alpha);
-        self.renderer.drawer().set_draw_color(color_struct);
+        self.renderer().drawer().set_draw_color(color_struct);
     }
 
-    // These functions are just aliases onto self.renderer.drawer() as you can see.
-    pub fn draw_rect(&self, rect: shape::Rect)      { self.renderer.drawer().draw_rect(rect) }
-    pub fn fill_rect(&self, rect: shape::Rect)      { self.renderer.drawer().fill_rect(rect) }
-    pub fn draw_point(&self, point: shape::Point)   { self.renderer.drawer().draw_point(point) }
+    // These functions are just aliases onto self.renderer().drawer() as you can see.
+    pub fn draw_rect(&self, rect: shape::Rect)      { self.renderer().drawer().draw_rect(rect) }
+    pub fn fill_rect(&self, rect: shape::Rect)      { self.renderer().drawer().fill_rect(rect) }
+    pub fn draw_point(&self, point: shape::Point)   { self.renderer().drawer().draw_point(point) }
 
     #[unstable]
     pub fn draw_polygon(&self, polygon: shape::Polygon) {
-        self.renderer.drawer().draw_points(&polygon.points[..])
+        self.renderer().drawer().draw_points(&polygon.points[..])
     }
 
     /// Display the image with its top-left corner at (x, y)
     pub fn draw_image(&self, image: &Image, x: i32, y: i32) {
-        self.renderer.drawer().copy(&((*image).texture), Some(shape::Rect{
+        self.renderer().drawer().copy(&image.texture, Some(shape::Rect{
             x: x,
             y: y,
             w: image.get_width(),
@@ -161,21 +377,45 @@ impl Window {
         }), None);
     }
 
+    /// Display the image stretched or shrunk to fill `dst`, instead of at its native size.
+    pub fn draw_image_scaled(&self, image: &Image, dst: shape::Rect) {
+        self.renderer().drawer().copy(&image.texture, None, Some(dst));
+    }
+
+    /// Display the image scaled to fill `dst`, rotated clockwise by `angle` degrees around its
+    /// center, and optionally mirrored horizontally and/or vertically.
+    pub fn draw_image_rotated(&self, image: &Image, dst: shape::Rect, angle: f64, flip_horizontal: bool, flip_vertical: bool) {
+        self.renderer().drawer().copy_ex(
+            &image.texture,
+            None,
+            Some(dst),
+            angle,
+            None,
+            (flip_horizontal, flip_vertical),
+        );
+    }
+
     /// Clear the screen to black. This will set the Window's draw color to (0,0,0,255)
     pub fn clear(&self) {
         self.set_color(0, 0, 0, 255);
-        self.renderer.drawer().clear();
+        self.renderer().drawer().clear();
     }
 }
 
-/// Image represents a bitmap that can be drawn on the screen.
-pub struct Image<'image> {
-    texture:    render::Texture<'image>,
-    width:      i32,
-    height:     i32,
+/// Image represents a bitmap that can be drawn on the screen. Unlike the renderer's raw textures,
+/// an Image owns everything it needs (including a handle keeping its Window's texture creator
+/// alive), so you can hold on to one for as long as you like instead of just for the duration of
+/// a single draw call.
+pub struct Image {
+    texture:            render::Texture,
+    // Keeps the texture creator (and transitively the renderer it came from) alive for as long as
+    // this Image exists. Never read, only held.
+    _texture_creator:   std::rc::Rc<render::TextureCreator>,
+    width:              i32,
+    height:             i32,
 }
 
-impl<'image> Image<'image> {
+impl Image {
     pub fn get_width(&self) -> i32  { self.width }
     pub fn get_height(&self) -> i32 { self.height }
 }
@@ -183,25 +423,127 @@ impl<'image> Image<'image> {
 /// Creation Methods
 /// ----------------
 impl Window {
-    // Load the image at the path you specify.
-    //
-    // TODO: work out the ownership issues with load_image and make it public.
-    #[allow(unused)]
-    fn load_image(&self, filename: Path) -> Result<Image,String> {
-        let texture = try!(LoadTexture::load_texture(&(self.renderer), &filename));
+    /// Load the image file at `filename`. The format (PNG, JPEG, BMP, GIF, ...) is detected
+    /// automatically from the file's contents, not its extension.
+    pub fn load_image(&self, filename: &str) -> Result<Image, String> {
+        let mut bytes = std::vec::Vec::new();
+        try!(
+            std::fs::File::open(filename)
+                .and_then(|mut file| std::io::Read::read_to_end(&mut file, &mut bytes))
+                .map_err(|e| e.to_string())
+        );
+        self.load_image_from_bytes(&bytes)
+    }
+
+    /// Decode an image that's already in memory (e.g. bytes embedded with `include_bytes!`, or
+    /// downloaded over the network) instead of read from a file. Format is auto-detected the same
+    /// way as `load_image`.
+    pub fn load_image_from_bytes(&self, bytes: &[u8]) -> Result<Image, String> {
+        let decoded = try!(image::load_from_memory(bytes).map_err(|e| e.to_string()));
+        self.image_from_decoded(decoded)
+    }
+
+    fn image_from_decoded(&self, decoded: image::DynamicImage) -> Result<Image, String> {
+        let rgba = decoded.to_rgba();
+        let (width, height) = rgba.dimensions();
+
+        let mut texture = try!(
+            self.texture_creator()
+                .create_texture_streaming(sdl2::pixels::PixelFormatEnum::RGBA32, width, height)
+                .map_err(|e| e.to_string())
+        );
+        try!(
+            texture.update(None, &rgba.into_raw(), (width * 4) as usize)
+                .map_err(|e| e.to_string())
+        );
+
         Ok(Image{
-            width:      texture.query().width,
-            height:     texture.query().height,
-            texture:    texture,
+            texture:            texture,
+            _texture_creator:   self.texture_creator().clone(),
+            width:              width as i32,
+            height:             height as i32,
+        })
+    }
+}
+
+/// A `PixelBuffer` gives direct, per-pixel control over an RGBA surface: the access pattern
+/// raytracers, cellular automata, and emulator displays want, instead of building everything from
+/// `draw_rect`/`draw_point` calls. Write into it with `set_pixel`/`fill`, then call `present` once
+/// per frame to upload it and blit it to fill the Window.
+pub struct PixelBuffer {
+    width:              u32,
+    height:             u32,
+    pixels:             std::vec::Vec<u8>, // RGBA, row-major, top-left origin
+    texture:            render::Texture,
+    _texture_creator:   std::rc::Rc<render::TextureCreator>,
+}
+
+impl PixelBuffer {
+    /// Create a buffer of the given size, initially filled with opaque black.
+    pub fn new(window: &Window, width: u32, height: u32) -> Result<PixelBuffer, String> {
+        let texture = try!(
+            window.texture_creator()
+                .create_texture_streaming(sdl2::pixels::PixelFormatEnum::RGBA32, width, height)
+                .map_err(|e| e.to_string())
+        );
+
+        let mut pixels = std::vec::Vec::with_capacity((width * height * 4) as usize);
+        for _ in 0..(width * height) {
+            pixels.extend_from_slice(&[0, 0, 0, 255]);
+        }
+
+        Ok(PixelBuffer{
+            width:              width,
+            height:             height,
+            pixels:             pixels,
+            texture:            texture,
+            _texture_creator:   window.texture_creator().clone(),
         })
     }
+
+    pub fn get_width(&self) -> u32  { self.width }
+    pub fn get_height(&self) -> u32 { self.height }
+
+    /// Set the color of a single pixel. Does nothing if `(x, y)` is out of bounds.
+    pub fn set_pixel(&mut self, x: u32, y: u32, r: u8, g: u8, b: u8, a: u8) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let offset = ((y * self.width + x) * 4) as usize;
+        self.pixels[offset]     = r;
+        self.pixels[offset + 1] = g;
+        self.pixels[offset + 2] = b;
+        self.pixels[offset + 3] = a;
+    }
+
+    /// Set every pixel in the buffer to the same color.
+    pub fn fill(&mut self, r: u8, g: u8, b: u8, a: u8) {
+        let mut offset = 0;
+        while offset < self.pixels.len() {
+            self.pixels[offset]     = r;
+            self.pixels[offset + 1] = g;
+            self.pixels[offset + 2] = b;
+            self.pixels[offset + 3] = a;
+            offset += 4;
+        }
+    }
+
+    /// Upload the buffer's current contents and blit it to fill the entire Window.
+    pub fn present(&mut self, window: &Window) -> Result<(), String> {
+        let pitch = (self.width * 4) as usize;
+        try!(self.texture.update(None, &self.pixels, pitch).map_err(|e| e.to_string()));
+        window.renderer().drawer().copy(&self.texture, None, None);
+        Ok(())
+    }
 }
 
 // Dtor for Window.
 impl std::ops::Drop for Window {
-    /// Close the window and clean up resources.
+    /// Close the window and clean up resources. Only tears down the shared SDL/SDL_image state
+    /// once every other Window has also been dropped.
     fn drop(&mut self) {
-        sdl2_image::quit();
+        PENDING_EVENTS.lock().unwrap().remove(&self.window_id);
+        release_sdl_context();
     }
 }
 